@@ -22,11 +22,17 @@ use proc_macro2::{
     Span,
     TokenStream as TokenStream2,
 };
+use std::collections::HashSet;
 use syn::{
     spanned::Spanned as _,
+    visit::Visit,
     Result,
 };
 
+/// The default maximum number of `#[ink(topic)]` fields an event variant may have,
+/// matching the standard environment's `MAX_EVENT_TOPICS`.
+const DEFAULT_MAX_EVENT_TOPICS: usize = 4;
+
 /// A checked ink! event definition.
 #[derive(Debug, PartialEq, Eq)]
 pub struct InkEventDefinition {
@@ -54,7 +60,7 @@ impl TryFrom<syn::ItemEnum> for InkEventDefinition {
             attrs: other_attrs,
             ..item_enum
         };
-        Self::new(item_enum, ink_attrs.is_anonymous())
+        Self::new(item_enum, ink_attrs.is_anonymous(), DEFAULT_MAX_EVENT_TOPICS)
     }
 }
 
@@ -68,34 +74,95 @@ impl quote::ToTokens for InkEventDefinition {
 
 impl InkEventDefinition {
     /// Returns `Ok` if the input matches all requirements for an ink! event definition.
-    pub fn new(item: syn::ItemEnum, anonymous: bool) -> Result<Self> {
+    ///
+    /// # Note
+    ///
+    /// Every variant and every field is checked independently of the others so that
+    /// all problems with the event definition are reported at once instead of only
+    /// the first one encountered. `max_topics` is the maximum number of
+    /// `#[ink(topic)]` fields any single variant may declare, usually the target
+    /// environment's `MAX_EVENT_TOPICS`.
+    pub fn new(item: syn::ItemEnum, anonymous: bool, max_topics: usize) -> Result<Self> {
+        let mut errors: Option<syn::Error> = None;
+        let mut push_error = |error: syn::Error| {
+            match &mut errors {
+                Some(errors) => errors.combine(error),
+                none => *none = Some(error),
+            }
+        };
+
         for variant in item.variants.iter() {
             'repeat: for field in variant.fields.iter() {
                 let field_span = field.span();
-                let (ink_attrs, _) = ir::partition_attributes(field.attrs.clone())?;
+                let ink_attrs = match ir::partition_attributes(field.attrs.clone()) {
+                    Ok((ink_attrs, _)) => ink_attrs,
+                    Err(error) => {
+                        push_error(error);
+                        continue 'repeat
+                    }
+                };
                 if ink_attrs.is_empty() {
                     continue 'repeat
                 }
-                let normalized =
-                    ir::InkAttribute::from_expanded(ink_attrs).map_err(|err| {
-                        err.into_combine(format_err!(field_span, "at this invocation",))
-                    })?;
+                let normalized = match ir::InkAttribute::from_expanded(ink_attrs) {
+                    Ok(normalized) => normalized,
+                    Err(error) => {
+                        push_error(
+                            error.into_combine(format_err!(field_span, "at this invocation",)),
+                        );
+                        continue 'repeat
+                    }
+                };
                 if !matches!(normalized.first().kind(), ir::AttributeArg::Topic) {
-                    return Err(format_err!(
+                    push_error(format_err!(
                         field_span,
                         "first optional ink! attribute of an event field must be #[ink(topic)]",
-                    ))
+                    ));
+                    continue 'repeat
                 }
                 for arg in normalized.args() {
                     if !matches!(arg.kind(), ir::AttributeArg::Topic) {
-                        return Err(format_err!(
+                        push_error(format_err!(
                             arg.span(),
                             "encountered conflicting ink! attribute for event field",
-                        ))
+                        ));
                     }
                 }
             }
         }
+
+        for (index, variant) in item.variants.iter().enumerate() {
+            let topic_fields: Vec<EventField> = EventVariant {
+                index,
+                item: variant,
+            }
+            .fields()
+            .filter(|field| field.is_topic)
+            .collect();
+            if topic_fields.len() <= max_topics {
+                continue
+            }
+            let mut error = format_err!(
+                variant.span(),
+                "ink! event variant has {} #[ink(topic)] fields, but the environment \
+                 only supports a maximum of {}",
+                topic_fields.len(),
+                max_topics,
+            );
+            for extra_topic in topic_fields.iter().skip(max_topics) {
+                error.combine(format_err!(
+                    extra_topic.span(),
+                    "exceeds the maximum number of {} event topics",
+                    max_topics,
+                ));
+            }
+            push_error(error);
+        }
+
+        if let Some(errors) = errors {
+            return Err(errors)
+        }
+
         Ok(Self {
             item,
             anonymous,
@@ -103,15 +170,59 @@ impl InkEventDefinition {
     }
 
     /// Returns `Ok` if the input matches all requirements for an ink! event definition.
+    ///
+    /// This is the entry point for the standalone `#[ink::event(..)]` form, as opposed
+    /// to the `#[ink(event)]` form nested inside an `#[ink::contract]` handled by
+    /// [`TryFrom<syn::ItemEnum>`]. Both forms share the same field validation in
+    /// [`Self::new`] and therefore agree on the resulting [`InkEventDefinition`].
     pub fn from_event_def_tokens(
         config: TokenStream2,
         input: TokenStream2,
     ) -> Result<Self> {
-        let _parsed_config = syn::parse2::<crate::ast::AttributeArgs>(config)?;
-        let anonymous = false; // todo parse this from attr config
+        let parsed_config = syn::parse2::<crate::ast::AttributeArgs>(config)?;
+        let (anonymous, max_topics) = Self::parse_event_args(parsed_config)?;
         let item = syn::parse2::<syn::ItemEnum>(input)?;
-        // let item = InkItemTrait::new(&config, parsed_item)?;
-        Ok(Self { anonymous, item })
+        Self::new(item, anonymous, max_topics)
+    }
+
+    /// Extracts the `anonymous` flag and the `max_topics` limit out of the standalone
+    /// event macro's attribute arguments, rejecting any other argument with a spanned
+    /// error. `max_topics` defaults to [`DEFAULT_MAX_EVENT_TOPICS`] if not specified,
+    /// allowing custom environments to raise or lower it.
+    fn parse_event_args(args: crate::ast::AttributeArgs) -> Result<(bool, usize)> {
+        let mut anonymous = false;
+        let mut max_topics = DEFAULT_MAX_EVENT_TOPICS;
+        for arg in args {
+            match arg {
+                syn::NestedMeta::Meta(syn::Meta::Path(path))
+                    if path.is_ident("anonymous") =>
+                {
+                    anonymous = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                    if name_value.path.is_ident("max_topics") =>
+                {
+                    match &name_value.lit {
+                        syn::Lit::Int(lit_int) => {
+                            max_topics = lit_int.base10_parse()?;
+                        }
+                        invalid => {
+                            return Err(format_err!(
+                                invalid.span(),
+                                "`max_topics` must be an integer literal",
+                            ))
+                        }
+                    }
+                }
+                other => {
+                    return Err(format_err!(
+                        other.span(),
+                        "encountered unknown ink! event attribute argument",
+                    ))
+                }
+            }
+        }
+        Ok((anonymous, max_topics))
     }
 
     /// Returns the identifier of the event struct.
@@ -139,6 +250,132 @@ impl InkEventDefinition {
             .max()
             .unwrap_or_default()
     }
+
+    /// Returns the `where` clause predicates required for the generated event code.
+    ///
+    /// For every type parameter of the event that is actually used by at least one
+    /// event field we add a `::ink::scale::Encode` bound. Type parameters that are
+    /// additionally used in a `#[ink(topic)]` field also receive the bound required
+    /// for topic encoding. Type parameters that only appear in phantom or otherwise
+    /// unused position receive no bound at all.
+    pub fn generic_bounds(&self) -> Vec<syn::WherePredicate> {
+        let all_fields: Vec<EventField> = self
+            .variants()
+            .flat_map(|variant| variant.fields().collect::<Vec<_>>())
+            .collect();
+
+        let mut bounds = Vec::new();
+        for ident in GenericTypeParams::from_generics(&self.item.generics).idents() {
+            let singleton = GenericTypeParams::singleton(ident.clone());
+            let is_used = all_fields.iter().any(|field| singleton.intersects(field.ty()));
+            if !is_used {
+                continue
+            }
+            bounds.push(syn::parse_quote!(#ident: ::ink::scale::Encode));
+            let is_used_as_topic = all_fields
+                .iter()
+                .any(|field| field.is_topic && singleton.intersects(field.ty()));
+            if is_used_as_topic {
+                bounds.push(syn::parse_quote!(#ident: ::ink::scale_info::TypeInfo));
+            }
+        }
+        bounds
+    }
+}
+
+/// The set of identifiers of an ink! event's type parameters.
+///
+/// Used to figure out which of an event's generic type parameters actually need a
+/// SCALE codec bound in the generated code, following the "params in scope" technique
+/// also used by `thiserror` to infer bounds for its derived `Error` impls.
+struct GenericTypeParams {
+    /// The identifiers in declaration order, so that bounds derived from them come
+    /// out in a deterministic, reproducible order across macro expansions.
+    idents: Vec<Ident>,
+    /// The same identifiers, kept in a set for `O(1)` membership checks in
+    /// [`Self::intersects`].
+    lookup: HashSet<Ident>,
+}
+
+impl GenericTypeParams {
+    /// Collects the identifiers of all type parameters declared by `generics`.
+    ///
+    /// Lifetime and const generic parameters are ignored, since they never need a
+    /// codec bound.
+    fn from_generics(generics: &syn::Generics) -> Self {
+        let idents: Vec<Ident> = generics
+            .type_params()
+            .map(|type_param| type_param.ident.clone())
+            .collect();
+        let lookup = idents.iter().cloned().collect();
+        Self { idents, lookup }
+    }
+
+    /// A set containing only `ident`, used to test a single type parameter at a time.
+    fn singleton(ident: Ident) -> Self {
+        Self {
+            idents: vec![ident.clone()],
+            lookup: std::iter::once(ident).collect(),
+        }
+    }
+
+    /// Returns an iterator over the identifiers in this set, in declaration order.
+    fn idents(&self) -> impl Iterator<Item = &Ident> {
+        self.idents.iter()
+    }
+
+    /// Returns `true` if `ty` mentions any of the identifiers in this set, recursing
+    /// into nested generic arguments (e.g. `Vec<T>` or `Option<Wrapper<T>>`).
+    /// Mentions inside a `PhantomData<_>` are not counted.
+    fn intersects(&self, ty: &syn::Type) -> bool {
+        struct Visitor<'a> {
+            params: &'a HashSet<Ident>,
+            found: bool,
+        }
+
+        impl<'a, 'ast> Visit<'ast> for Visitor<'a> {
+            fn visit_type(&mut self, ty: &'ast syn::Type) {
+                // A type parameter that only ever appears inside a `PhantomData<_>`
+                // is never actually encoded, so it doesn't need a codec bound. Don't
+                // recurse into it, or `PhantomData<T>` would be (incorrectly)
+                // treated the same as a real usage of `T`.
+                if is_phantom_data(ty) {
+                    return
+                }
+                syn::visit::visit_type(self, ty);
+            }
+
+            fn visit_path(&mut self, path: &'ast syn::Path) {
+                if let Some(segment) = path.segments.first() {
+                    if self.params.contains(&segment.ident) {
+                        self.found = true;
+                    }
+                }
+                syn::visit::visit_path(self, path);
+            }
+        }
+
+        fn is_phantom_data(ty: &syn::Type) -> bool {
+            match ty {
+                syn::Type::Path(type_path) => {
+                    type_path
+                        .path
+                        .segments
+                        .last()
+                        .map(|segment| segment.ident == "PhantomData")
+                        .unwrap_or(false)
+                }
+                _ => false,
+            }
+        }
+
+        let mut visitor = Visitor {
+            params: &self.lookup,
+            found: false,
+        };
+        visitor.visit_type(ty);
+        visitor.found
+    }
 }
 
 /// A variant of an event.
@@ -298,18 +535,91 @@ mod tests {
     }
 
     #[test]
-    fn generic_event_fails() {
-        assert_try_from_fails(
-            syn::parse_quote! {
-                #[ink(event)]
-                pub struct GenericEvent<T> {
-                    #[ink(topic)]
-                    field_1: T,
-                    field_2: bool,
-                }
-            },
-            "generic ink! event structs are not supported",
-        )
+    fn generic_event_struct_works() {
+        let item_struct: syn::ItemStruct = syn::parse_quote! {
+            #[ink(event)]
+            pub struct GenericEvent<T> {
+                #[ink(topic)]
+                field_1: T,
+                field_2: bool,
+            }
+        };
+        assert!(InkEventDefinition::try_from(item_struct).is_ok());
+    }
+
+    #[test]
+    fn generic_bounds_only_cover_used_type_params() {
+        let item_struct: syn::ItemStruct = syn::parse_quote! {
+            #[ink(event)]
+            pub struct GenericEvent<T, U, V> {
+                #[ink(topic)]
+                field_1: T,
+                field_2: Vec<U>,
+                field_3: bool,
+            }
+        };
+        let event = InkEventDefinition::try_from(item_struct).unwrap();
+        let bounds = event
+            .generic_bounds()
+            .iter()
+            .map(|bound| quote::quote!(#bound).to_string())
+            .collect::<Vec<_>>();
+
+        assert!(bounds.contains(&quote::quote!(T : :: ink :: scale :: Encode).to_string()));
+        assert!(bounds.contains(&quote::quote!(T : :: ink :: scale_info :: TypeInfo).to_string()));
+        assert!(bounds.contains(&quote::quote!(U : :: ink :: scale :: Encode).to_string()));
+        assert!(!bounds
+            .iter()
+            .any(|bound| bound.contains("U : :: ink :: scale_info :: TypeInfo")));
+        // `V` is unused by any field and therefore needs no bound at all.
+        assert!(!bounds.iter().any(|bound| bound.starts_with("V :")));
+    }
+
+    #[test]
+    fn generic_bounds_are_in_declaration_order() {
+        // Bounds must come out in the type parameters' declaration order on every
+        // invocation, not in whatever order a `HashSet` happens to iterate in, since
+        // the generated code must be reproducible across macro expansions.
+        let item_struct: syn::ItemStruct = syn::parse_quote! {
+            #[ink(event)]
+            pub struct GenericEvent<T, U> {
+                #[ink(topic)]
+                field_1: T,
+                field_2: U,
+            }
+        };
+        let event = InkEventDefinition::try_from(item_struct).unwrap();
+        let bounds = event
+            .generic_bounds()
+            .iter()
+            .map(|bound| quote::quote!(#bound).to_string())
+            .collect::<Vec<_>>();
+
+        let t_pos = bounds
+            .iter()
+            .position(|bound| bound.starts_with("T :"))
+            .unwrap();
+        let u_pos = bounds
+            .iter()
+            .position(|bound| bound.starts_with("U :"))
+            .unwrap();
+        assert!(t_pos < u_pos);
+    }
+
+    #[test]
+    fn generic_bounds_skip_phantom_type_params() {
+        // `T` only ever appears inside a `PhantomData<T>` marker field, so it is
+        // never actually encoded and must not receive a codec bound.
+        let item_struct: syn::ItemStruct = syn::parse_quote! {
+            #[ink(event)]
+            pub struct GenericEvent<T> {
+                #[ink(topic)]
+                field_1: i32,
+                marker: core::marker::PhantomData<T>,
+            }
+        };
+        let event = InkEventDefinition::try_from(item_struct).unwrap();
+        assert!(event.generic_bounds().is_empty());
     }
 
     #[test]
@@ -374,6 +684,23 @@ mod tests {
         )
     }
 
+    #[test]
+    fn multiple_field_errors_are_accumulated() {
+        let item_struct: syn::ItemStruct = syn::parse_quote! {
+            #[ink(event)]
+            pub struct MyEvent {
+                #[ink(message)]
+                field_1: i32,
+                #[ink(topic)]
+                #[ink(payable)]
+                field_2: bool,
+            }
+        };
+        let err = InkEventDefinition::try_from(item_struct).unwrap_err();
+        let compile_error = err.to_compile_error().to_string();
+        assert_eq!(compile_error.matches("compile_error").count(), 2);
+    }
+
     /// Used for the event fields iterator unit test because `syn::Field` does
     /// not provide a `syn::parse::Parse` implementation.
     #[derive(Debug, PartialEq, Eq)]
@@ -469,4 +796,145 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn from_event_def_tokens_agrees_with_in_enum_form() {
+        let item_enum: syn::ItemEnum = syn::parse_quote! {
+            pub enum MyEvent {
+                A {
+                    #[ink(topic)]
+                    field_1: i32,
+                    field_2: bool,
+                },
+            }
+        };
+        let via_tokens = InkEventDefinition::from_event_def_tokens(
+            quote::quote!(anonymous),
+            quote::quote!(#item_enum),
+        )
+        .unwrap();
+        let via_new =
+            InkEventDefinition::new(item_enum, true, DEFAULT_MAX_EVENT_TOPICS).unwrap();
+        assert_eq!(via_tokens, via_new);
+        assert!(via_tokens.anonymous);
+    }
+
+    #[test]
+    fn from_event_def_tokens_without_anonymous_arg_works() {
+        let item_enum: syn::ItemEnum = syn::parse_quote! {
+            pub enum MyEvent {
+                A {
+                    #[ink(topic)]
+                    field_1: i32,
+                    field_2: bool,
+                },
+            }
+        };
+        let event = InkEventDefinition::from_event_def_tokens(
+            quote::quote!(),
+            quote::quote!(#item_enum),
+        )
+        .unwrap();
+        assert!(!event.anonymous);
+    }
+
+    #[test]
+    fn from_event_def_tokens_rejects_unknown_arg() {
+        let item_enum: syn::ItemEnum = syn::parse_quote! {
+            pub enum MyEvent {
+                A {
+                    #[ink(topic)]
+                    field_1: i32,
+                },
+            }
+        };
+        let err = InkEventDefinition::from_event_def_tokens(
+            quote::quote!(non_existent),
+            quote::quote!(#item_enum),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "encountered unknown ink! event attribute argument",
+        );
+    }
+
+    #[test]
+    fn variant_within_max_topics_works() {
+        let item_enum: syn::ItemEnum = syn::parse_quote! {
+            pub enum MyEvent {
+                A {
+                    #[ink(topic)]
+                    field_1: i32,
+                    #[ink(topic)]
+                    field_2: i32,
+                },
+            }
+        };
+        assert!(InkEventDefinition::new(item_enum, false, 2).is_ok());
+    }
+
+    #[test]
+    fn variant_exceeding_max_topics_fails() {
+        let item_enum: syn::ItemEnum = syn::parse_quote! {
+            pub enum MyEvent {
+                A {
+                    #[ink(topic)]
+                    field_1: i32,
+                    #[ink(topic)]
+                    field_2: i32,
+                    #[ink(topic)]
+                    field_3: i32,
+                },
+            }
+        };
+        let err = InkEventDefinition::new(item_enum, false, 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ink! event variant has 3 #[ink(topic)] fields, but the environment only \
+             supports a maximum of 2",
+        );
+    }
+
+    #[test]
+    fn anonymous_variant_exceeding_max_topics_fails() {
+        // The max-topics check must run regardless of the `anonymous` flag.
+        let item_enum: syn::ItemEnum = syn::parse_quote! {
+            pub enum MyEvent {
+                A {
+                    #[ink(topic)]
+                    field_1: i32,
+                    #[ink(topic)]
+                    field_2: i32,
+                    #[ink(topic)]
+                    field_3: i32,
+                },
+            }
+        };
+        let err = InkEventDefinition::new(item_enum, true, 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ink! event variant has 3 #[ink(topic)] fields, but the environment only \
+             supports a maximum of 2",
+        );
+    }
+
+    #[test]
+    fn from_event_def_tokens_with_custom_max_topics_works() {
+        let item_enum: syn::ItemEnum = syn::parse_quote! {
+            pub enum MyEvent {
+                A {
+                    #[ink(topic)]
+                    field_1: i32,
+                    #[ink(topic)]
+                    field_2: i32,
+                },
+            }
+        };
+        assert!(InkEventDefinition::from_event_def_tokens(
+            quote::quote!(max_topics = 2),
+            quote::quote!(#item_enum),
+        )
+        .is_ok());
+    }
 }
\ No newline at end of file